@@ -0,0 +1,8 @@
+use anyhow::Result;
+use rusoto_core::{credential, request, Region};
+
+pub fn new_client<C>(new_with: impl FnOnce(request::HttpClient, credential::ChainProvider, Region) -> C) -> Result<C> {
+    let dispatcher = request::HttpClient::new()?;
+    let creds = credential::ChainProvider::new();
+    Ok(new_with(dispatcher, creds, Region::default()))
+}
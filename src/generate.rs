@@ -1,9 +1,8 @@
-use crate::tts::{TTSVoice, TTS};
+use crate::tts::{Backend, OutputFormat, SpeechMark, SynthInput, SynthesisOpts, VoiceFilter, TTS};
 use anyhow::{bail, Result};
 use fasthash::XXHasher;
 use std::{
     collections::{BTreeMap, BTreeSet},
-    convert::TryFrom,
     fs::File,
     hash::{Hash, Hasher},
     path::PathBuf,
@@ -38,6 +37,40 @@ pub struct Opts {
 
     #[structopt(long, help = "Overwrite existing files in audio directory")]
     pub force: bool,
+
+    #[structopt(
+        long,
+        default_value = "polly",
+        help = "Speech synthesis backend to use (polly, offline)"
+    )]
+    pub backend: Backend,
+
+    #[structopt(
+        long,
+        help = "Also write a parrot_<hash>.json speech-mark sidecar with per-word timing"
+    )]
+    pub speech_marks: bool,
+
+    #[structopt(
+        long,
+        help = "Treat every field as SSML (auto-detected when a field starts with <speak>)"
+    )]
+    pub ssml: bool,
+
+    #[structopt(long, default_value = "mp3", help = "Audio output format (mp3, ogg, pcm)")]
+    pub format: OutputFormat,
+
+    #[structopt(
+        long,
+        help = "Audio sample rate in Hz; valid values depend on --format and --neural"
+    )]
+    pub sample_rate: Option<String>,
+
+    #[structopt(
+        long,
+        help = "Pronunciation lexicon name(s) to apply (Polly backend only, see the lexicon subcommand)"
+    )]
+    pub lexicon: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -47,12 +80,29 @@ struct WorkItem {
     sentence_hash: u64,
     record_hash: u64,
     output_path: PathBuf,
+    marks_path: PathBuf,
+    text_type: Option<String>,
 }
 
 impl WorkItem {
-    pub fn new_from_record(seq: usize, record: csv::StringRecord) -> Self {
+    pub fn new_from_record(
+        seq: usize,
+        record: csv::StringRecord,
+        force_ssml: bool,
+        format: OutputFormat,
+        lexicons: &[String],
+    ) -> Self {
+        let text_type = if force_ssml || record[0].trim_start().starts_with("<speak>") {
+            Some("ssml".to_string())
+        } else {
+            None
+        };
+        let mut sorted_lexicons = lexicons.to_vec();
+        sorted_lexicons.sort();
         let mut hasher = XXHasher::default();
         record[0].hash(&mut hasher);
+        text_type.hash(&mut hasher);
+        sorted_lexicons.hash(&mut hasher);
         let sentence_hash = hasher.finish();
         let mut record_hash = sentence_hash;
         if record.len() > 1 {
@@ -60,22 +110,27 @@ impl WorkItem {
             for field in record.iter() {
                 field.hash(&mut hasher);
             }
+            text_type.hash(&mut hasher);
+            sorted_lexicons.hash(&mut hasher);
             record_hash = hasher.finish();
         }
-        let output_path = format!("parrot_{}.mp3", sentence_hash).into();
+        let output_path = format!("parrot_{}.{}", sentence_hash, format.extension()).into();
+        let marks_path = format!("parrot_{}.json", sentence_hash).into();
         WorkItem {
             seq,
             record,
             sentence_hash,
             record_hash,
             output_path,
+            marks_path,
+            text_type,
         }
     }
 }
 
 #[derive(Debug)]
 struct WorkBundle {
-    needs_tts: BTreeMap<u64, String>,
+    needs_tts: BTreeMap<u64, SynthInput>,
     work_items: Vec<WorkItem>,
 }
 
@@ -88,9 +143,10 @@ impl WorkBundle {
     }
 
     fn add_work_item(&mut self, wi: WorkItem) {
-        self.needs_tts
-            .entry(wi.sentence_hash)
-            .or_insert_with(|| wi.record[0].to_string());
+        self.needs_tts.entry(wi.sentence_hash).or_insert_with(|| SynthInput {
+            text: wi.record[0].to_string(),
+            text_type: wi.text_type.clone(),
+        });
         self.work_items.push(wi);
     }
 }
@@ -127,7 +183,13 @@ pub async fn exec(tts: TTS, options: Opts) -> Result<()> {
         .enumerate()
         // Filter out already seen sentences.
         .filter_map(|(seq, record)| {
-            let wi = WorkItem::new_from_record(seq, record);
+            let wi = WorkItem::new_from_record(
+                seq,
+                record,
+                options.ssml,
+                options.format,
+                &options.lexicon,
+            );
             if (options.force && wi.output_path.exists()) || seen.contains(&wi.record_hash) {
                 None
             } else {
@@ -144,25 +206,28 @@ pub async fn exec(tts: TTS, options: Opts) -> Result<()> {
         });
 
     let maybe_voice = tts
-        .list_voices(None)
+        .list_voices(&VoiceFilter::default())
         .await?
         .into_iter()
-        .filter(|v| {
-            if let Some(vid) = &v.id {
-                vid.to_lowercase() == options.voice.to_lowercase()
-            } else {
-                false
-            }
-        })
-        .map(TTSVoice::try_from)
-        .collect::<Result<Vec<TTSVoice>>>()?
-        .into_iter()
-        .find(|v| !options.neural || v.neural);
+        .find(|v| v.id.to_lowercase() == options.voice.to_lowercase() && (!options.neural || v.neural));
 
     if let Some(voice) = maybe_voice {
-        let results = tts
-            .generate_many(&work.needs_tts, &voice, options.neural)
-            .await?;
+        let opts = SynthesisOpts {
+            neural: options.neural,
+            output_format: options.format,
+            sample_rate: options.sample_rate.clone(),
+            lexicon_names: if options.lexicon.is_empty() {
+                None
+            } else {
+                Some(options.lexicon.clone())
+            },
+        };
+        let results = tts.generate_many(&work.needs_tts, &voice, &opts).await?;
+        let marks: BTreeMap<u64, Vec<SpeechMark>> = if options.speech_marks {
+            tts.marks_many(&work.needs_tts, &voice, &opts).await?
+        } else {
+            BTreeMap::new()
+        };
         // Open the output file for writing.
         let mut wb = csv::WriterBuilder::new();
         if options.tabs {
@@ -180,6 +245,19 @@ pub async fn exec(tts: TTS, options: Opts) -> Result<()> {
                 let output_path_str =
                     format!("[sound:{}]", wi.output_path.as_os_str().to_string_lossy());
                 output_row.push_field(output_path_str.as_str());
+                if options.speech_marks {
+                    if let Some(sentence_marks) = marks.get(&wi.sentence_hash) {
+                        let marks_file_path = options.audio_directory.join(&wi.marks_path);
+                        if options.force || !marks_file_path.exists() {
+                            let marks_json = serde_json::to_vec(sentence_marks)?;
+                            let mut marks_file = AsyncFile::create(&marks_file_path).await?;
+                            marks_file.write_all(&marks_json).await?;
+                        }
+                        output_row.push_field(&wi.marks_path.as_os_str().to_string_lossy());
+                    } else {
+                        bail!("Couldn't find speech marks for {}", wi.sentence_hash);
+                    }
+                }
                 writer.write_record(&output_row)?;
             } else {
                 bail!("Couldn't find result for {}", wi.sentence_hash);
@@ -0,0 +1,58 @@
+use crate::aws;
+use anyhow::Result;
+use rusoto_polly::{DeleteLexiconInput, ListLexiconsInput, Polly, PollyClient, PutLexiconInput};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(no_version, about = "Manage Amazon Polly pronunciation lexicons")]
+pub enum Opts {
+    /// Upload a local PLS lexicon file under a name
+    Put {
+        #[structopt(help = "Name to store the lexicon under")]
+        name: String,
+
+        #[structopt(parse(from_os_str), help = "Path to a PLS lexicon file")]
+        file: PathBuf,
+    },
+    /// List lexicons available in this account and region
+    List,
+    /// Delete a lexicon by name
+    Delete {
+        #[structopt(help = "Name of the lexicon to delete")]
+        name: String,
+    },
+}
+
+pub async fn exec(options: Opts) -> Result<()> {
+    let client = aws::new_client(PollyClient::new_with)?;
+    match options {
+        Opts::Put { name, file } => {
+            let content = tokio::fs::read_to_string(&file).await?;
+            client.put_lexicon(PutLexiconInput { name, content }).await?;
+            println!("Uploaded lexicon.");
+        }
+        Opts::List => {
+            let result = client
+                .list_lexicons(ListLexiconsInput { next_token: None })
+                .await?;
+            for lexicon in result.lexicons.unwrap_or_default() {
+                let name = lexicon.name.unwrap_or_default();
+                match lexicon.attributes {
+                    Some(attrs) => println!(
+                        "{} ({} entries, {})",
+                        name,
+                        attrs.lexemes_count.unwrap_or_default(),
+                        attrs.language_code.unwrap_or_default()
+                    ),
+                    None => println!("{}", name),
+                }
+            }
+        }
+        Opts::Delete { name } => {
+            client.delete_lexicon(DeleteLexiconInput { name }).await?;
+            println!("Deleted lexicon.");
+        }
+    }
+    Ok(())
+}
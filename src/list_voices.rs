@@ -1,24 +1,42 @@
-use crate::tts::{TTSVoice, TTS};
+use crate::tts::{Backend, Gender, VoiceFilter, TTS};
 use anyhow::Result;
 use itertools::Itertools;
-use std::convert::TryFrom;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-#[structopt(no_version, about = "List all available AWS Polly voices")]
+#[structopt(no_version, about = "List all available voices")]
 pub struct Opts {
-    #[structopt(short, long, help = "Only show voices for this language")]
+    #[structopt(
+        short,
+        long,
+        help = "Only show voices whose language subtag matches this BCP-47 tag, e.g. en (region and other subtags are ignored, so en-US also lists en-GB)"
+    )]
     pub language: Option<String>,
+
+    #[structopt(long, help = "Only show voices of this gender (male, female, other)")]
+    pub gender: Option<Gender>,
+
+    #[structopt(long, help = "Only show voices that support the neural engine")]
+    pub neural_only: bool,
+
+    #[structopt(
+        long,
+        default_value = "polly",
+        help = "Speech synthesis backend to use (polly, offline)"
+    )]
+    pub backend: Backend,
 }
 
 pub async fn exec(tts: TTS, options: Opts) -> Result<()> {
+    let filter = VoiceFilter {
+        language: options.language.map(|l| l.parse()).transpose()?,
+        gender: options.gender,
+        neural_only: options.neural_only,
+    };
     let voices = tts
-        .list_voices(options.language)
+        .list_voices(&filter)
         .await?
         .into_iter()
-        .map(TTSVoice::try_from)
-        .collect::<Result<Vec<TTSVoice>>>()?
-        .into_iter()
         .map(|v| (v.language.clone(), v))
         .into_group_map();
 
@@ -29,10 +47,10 @@ pub async fn exec(tts: TTS, options: Opts) -> Result<()> {
             println!("\n===== {}\n", key);
             for voice in voices {
                 let id = voice.id.as_str();
-                let gender = match voice.gender.to_lowercase().as_str() {
-                    "male" => "♂",
-                    "female" => "♀",
-                    _ => "?",
+                let gender = match voice.gender {
+                    Gender::Male => "♂",
+                    Gender::Female => "♀",
+                    Gender::Other => "?",
                 };
                 let neural = match voice.neural {
                     true => "supports neural",
@@ -2,8 +2,12 @@ use crate::tts::TTS;
 use anyhow::Result;
 use structopt::StructOpt;
 
+mod aws;
 mod generate;
+mod lexicon;
 mod list_voices;
+mod translate;
+mod translator;
 mod tts;
 
 #[derive(Debug, StructOpt)]
@@ -11,13 +15,28 @@ mod tts;
 enum Command {
     Generate(generate::Opts),
     ListVoices(list_voices::Opts),
+    Translate(translate::Opts),
+    Lexicon(lexicon::Opts),
 }
 
 async fn main_impl(options: Command) -> Result<()> {
-    let tts = TTS::new()?;
     match options {
-        Command::Generate(opts) => generate::exec(tts, opts).await?,
-        Command::ListVoices(opts) => list_voices::exec(tts, opts).await?,
+        Command::Generate(opts) => {
+            let tts = TTS::new(opts.backend)?;
+            generate::exec(tts, opts).await?
+        }
+        Command::ListVoices(opts) => {
+            let tts = TTS::new(opts.backend)?;
+            list_voices::exec(tts, opts).await?
+        }
+        Command::Translate(opts) => {
+            let tts = match opts.voice {
+                Some(_) => Some(TTS::new(opts.backend)?),
+                None => None,
+            };
+            translate::exec(tts, opts).await?
+        }
+        Command::Lexicon(opts) => lexicon::exec(opts).await?,
     }
     Ok(())
 }
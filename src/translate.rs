@@ -0,0 +1,207 @@
+use crate::translator::Translator;
+use crate::tts::{Backend, SynthInput, SynthesisOpts, VoiceFilter, TTS};
+use anyhow::{bail, Result};
+use fasthash::XXHasher;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+use structopt::StructOpt;
+use tokio::fs::File as AsyncFile;
+use tokio::prelude::*;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    no_version,
+    about = "Build a bilingual deck by translating a single-language source with AWS Translate"
+)]
+pub struct Opts {
+    #[structopt(parse(from_os_str), help = "Source file, one sentence per row")]
+    pub source: PathBuf,
+
+    #[structopt(parse(from_os_str), help = "Target file")]
+    pub target: PathBuf,
+
+    #[structopt(long, help = "Source language code, e.g. en")]
+    pub source_lang: String,
+
+    #[structopt(long, help = "Target language code, e.g. fr")]
+    pub target_lang: String,
+
+    #[structopt(long, help = "TSV instead of CSV")]
+    pub tabs: bool,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Directory to write TTS audio for the translation; omit to skip TTS"
+    )]
+    pub audio_directory: Option<PathBuf>,
+
+    #[structopt(
+        short,
+        long,
+        help = "Voice ID of the selected --backend for the translation audio (requires --audio-directory)"
+    )]
+    pub voice: Option<String>,
+
+    #[structopt(long, help = "Use the neural voice (voice must support it)")]
+    pub neural: bool,
+
+    #[structopt(
+        long,
+        default_value = "polly",
+        help = "Speech synthesis backend to use (polly, offline)"
+    )]
+    pub backend: Backend,
+}
+
+#[derive(Debug)]
+struct WorkItem {
+    seq: usize,
+    sentence: String,
+    sentence_hash: u64,
+    output_path: PathBuf,
+}
+
+impl WorkItem {
+    fn new_from_record(
+        seq: usize,
+        record: csv::StringRecord,
+        source_lang: &str,
+        target_lang: &str,
+        voice: Option<&str>,
+    ) -> Self {
+        let sentence = record[0].to_string();
+        let mut hasher = XXHasher::default();
+        sentence.hash(&mut hasher);
+        source_lang.hash(&mut hasher);
+        target_lang.hash(&mut hasher);
+        voice.hash(&mut hasher);
+        let sentence_hash = hasher.finish();
+        let output_path = format!("parrot_{}.mp3", sentence_hash).into();
+        WorkItem {
+            seq,
+            sentence,
+            sentence_hash,
+            output_path,
+        }
+    }
+}
+
+fn get_csv_reader(options: &Opts) -> Result<csv::Reader<File>> {
+    let mut rdr_builder = csv::ReaderBuilder::new();
+    if options.tabs {
+        rdr_builder.delimiter(b'\t');
+    }
+    let reader = rdr_builder.from_path(&options.source)?;
+    Ok(reader)
+}
+
+async fn read_source(options: &Opts) -> Result<Vec<csv::StringRecord>> {
+    let reader = get_csv_reader(&options)?;
+    reader
+        .into_records()
+        .map(|r| {
+            let rc = r?;
+            if rc.is_empty() {
+                bail!("All rows in the source must have at least one field");
+            }
+            Ok(rc)
+        })
+        .collect::<Result<Vec<csv::StringRecord>>>()
+}
+
+pub async fn exec(tts: Option<TTS>, options: Opts) -> Result<()> {
+    if options.voice.is_some() != options.audio_directory.is_some() {
+        bail!("--voice and --audio-directory must be given together");
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut needs_translate: BTreeMap<u64, String> = BTreeMap::new();
+    let mut work_items = Vec::new();
+    for (seq, record) in read_source(&options).await?.into_iter().enumerate() {
+        let wi = WorkItem::new_from_record(
+            seq,
+            record,
+            &options.source_lang,
+            &options.target_lang,
+            options.voice.as_deref(),
+        );
+        if seen.insert(wi.sentence_hash) {
+            needs_translate.insert(wi.sentence_hash, wi.sentence.clone());
+        }
+        work_items.push(wi);
+    }
+
+    let translations = Translator::new()?
+        .translate_many(&needs_translate, &options.source_lang, &options.target_lang)
+        .await?;
+
+    let audio = if let (Some(tts), Some(voice_id), Some(audio_directory)) =
+        (&tts, &options.voice, &options.audio_directory)
+    {
+        let maybe_voice = tts
+            .list_voices(&VoiceFilter::default())
+            .await?
+            .into_iter()
+            .find(|v| v.id.to_lowercase() == voice_id.to_lowercase() && (!options.neural || v.neural));
+        let voice = match maybe_voice {
+            Some(voice) => voice,
+            None => bail!("Couldn't find voice {}", voice_id),
+        };
+        let opts = SynthesisOpts {
+            neural: options.neural,
+            ..Default::default()
+        };
+        let needs_tts = translations
+            .iter()
+            .map(|(k, v)| {
+                (
+                    *k,
+                    SynthInput {
+                        text: v.clone(),
+                        text_type: None,
+                    },
+                )
+            })
+            .collect::<BTreeMap<u64, SynthInput>>();
+        Some((tts.generate_many(&needs_tts, &voice, &opts).await?, audio_directory))
+    } else {
+        None
+    };
+
+    let mut wb = csv::WriterBuilder::new();
+    if options.tabs {
+        wb.delimiter(b'\t');
+    }
+    let mut writer = wb.from_path(&options.target)?;
+
+    for wi in work_items.iter() {
+        let translation = match translations.get(&wi.sentence_hash) {
+            Some(t) => t,
+            None => bail!("Couldn't find translation for {}", wi.sentence_hash),
+        };
+        let mut output_row = csv::StringRecord::new();
+        output_row.push_field(&wi.sentence);
+        output_row.push_field(translation);
+        if let Some((audio, audio_directory)) = &audio {
+            let bytes = match audio.get(&wi.sentence_hash) {
+                Some(bytes) => bytes,
+                None => bail!("Couldn't find audio for {}", wi.sentence_hash),
+            };
+            let output_path = audio_directory.join(&wi.output_path);
+            if !output_path.exists() {
+                let mut file = AsyncFile::create(&output_path).await?;
+                file.write_all(bytes).await?;
+            }
+            let output_path_str = format!("[sound:{}]", wi.output_path.as_os_str().to_string_lossy());
+            output_row.push_field(output_path_str.as_str());
+        }
+        writer.write_record(&output_row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::aws;
+use anyhow::Result;
+use futures::future::join_all;
+use rusoto_translate::{Translate, TranslateClient, TranslateTextRequest};
+use std::collections::BTreeMap;
+
+pub struct Translator {
+    client: TranslateClient,
+}
+
+impl Translator {
+    pub fn new() -> Result<Translator> {
+        let client = aws::new_client(TranslateClient::new_with)?;
+        Ok(Translator { client })
+    }
+
+    pub async fn translate_many(
+        &self,
+        tasks: &BTreeMap<u64, String>,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<BTreeMap<u64, String>> {
+        Ok(join_all(
+            tasks
+                .iter()
+                .map(|(k, v)| self.translate_one(*k, v.clone(), source_lang, target_lang)),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<Vec<(u64, String)>>>()?
+        .into_iter()
+        .collect::<BTreeMap<u64, String>>())
+    }
+
+    pub async fn translate_one(
+        &self,
+        key: u64,
+        text: String,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<(u64, String)> {
+        let request = TranslateTextRequest {
+            text,
+            source_language_code: source_lang.to_string(),
+            target_language_code: target_lang.to_string(),
+            terminology_names: None,
+        };
+        let result = self.client.translate_text(request).await?;
+        Ok((key, result.translated_text))
+    }
+}
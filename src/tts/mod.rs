@@ -0,0 +1,237 @@
+mod offline;
+mod polly;
+
+pub use offline::EspeakSynthesizer;
+pub use polly::PollySynthesizer;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::join_all;
+use std::{collections::BTreeMap, str::FromStr};
+use unic_langid::LanguageIdentifier;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+pub enum Gender {
+    Male,
+    Female,
+    Other,
+}
+
+impl FromStr for Gender {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "male" => Ok(Gender::Male),
+            "female" => Ok(Gender::Female),
+            "other" => Ok(Gender::Other),
+            other => bail!("Unknown gender '{}' (expected 'male', 'female', or 'other')", other),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct TTSVoice {
+    pub id: String,
+    pub gender: Gender,
+    pub language: String,
+    pub code: LanguageIdentifier,
+    pub neural: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VoiceFilter {
+    pub language: Option<LanguageIdentifier>,
+    pub gender: Option<Gender>,
+    pub neural_only: bool,
+}
+
+impl VoiceFilter {
+    pub fn matches(&self, voice: &TTSVoice) -> bool {
+        if let Some(language) = &self.language {
+            if voice.code.language != language.language {
+                return false;
+            }
+        }
+        if let Some(gender) = self.gender {
+            if voice.gender != gender {
+                return false;
+            }
+        }
+        if self.neural_only && !voice.neural {
+            return false;
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SpeechMark {
+    pub time: u64,
+    #[serde(rename = "type")]
+    pub mark_type: String,
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Mp3,
+    Ogg,
+    Pcm,
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Mp3 => "mp3",
+            OutputFormat::Ogg => "ogg",
+            OutputFormat::Pcm => "pcm",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mp3" => Ok(OutputFormat::Mp3),
+            "ogg" => Ok(OutputFormat::Ogg),
+            "pcm" => Ok(OutputFormat::Pcm),
+            other => bail!("Unknown output format '{}' (expected 'mp3', 'ogg', or 'pcm')", other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SynthesisOpts {
+    pub neural: bool,
+    pub output_format: OutputFormat,
+    pub sample_rate: Option<String>,
+    pub lexicon_names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SynthInput {
+    pub text: String,
+    pub text_type: Option<String>,
+}
+
+#[async_trait]
+pub trait SpeechSynthesizer {
+    async fn synthesize(&self, input: &SynthInput, voice: &TTSVoice, opts: &SynthesisOpts) -> Result<Bytes>;
+    async fn list_voices(&self) -> Result<Vec<TTSVoice>>;
+
+    async fn synthesize_marks(
+        &self,
+        _input: &SynthInput,
+        _voice: &TTSVoice,
+        _opts: &SynthesisOpts,
+    ) -> Result<Vec<SpeechMark>> {
+        bail!("This backend does not support speech marks")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Backend {
+    Polly,
+    Offline,
+}
+
+impl FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "polly" => Ok(Backend::Polly),
+            "offline" => Ok(Backend::Offline),
+            other => bail!("Unknown backend '{}' (expected 'polly' or 'offline')", other),
+        }
+    }
+}
+
+pub struct TTS {
+    backend: Box<dyn SpeechSynthesizer + Send + Sync>,
+}
+
+impl TTS {
+    pub fn new(backend: Backend) -> Result<TTS> {
+        let backend: Box<dyn SpeechSynthesizer + Send + Sync> = match backend {
+            Backend::Polly => Box::new(PollySynthesizer::new()?),
+            Backend::Offline => Box::new(EspeakSynthesizer::new()?),
+        };
+        Ok(TTS { backend })
+    }
+
+    pub async fn list_voices(&self, filter: &VoiceFilter) -> Result<Vec<TTSVoice>> {
+        Ok(self
+            .backend
+            .list_voices()
+            .await?
+            .into_iter()
+            .filter(|v| filter.matches(v))
+            .collect())
+    }
+
+    pub async fn generate_many(
+        &self,
+        tasks: &BTreeMap<u64, SynthInput>,
+        voice: &TTSVoice,
+        opts: &SynthesisOpts,
+    ) -> Result<BTreeMap<u64, Bytes>> {
+        Ok(join_all(
+            tasks
+                .iter()
+                .map(|(k, v)| self.generate_one(*k, v.clone(), voice, opts)),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<Vec<(u64, Bytes)>>>()?
+        .into_iter()
+        .collect::<BTreeMap<u64, Bytes>>())
+    }
+
+    pub async fn generate_one(
+        &self,
+        key: u64,
+        input: SynthInput,
+        voice: &TTSVoice,
+        opts: &SynthesisOpts,
+    ) -> Result<(u64, Bytes)> {
+        let bytes = self.backend.synthesize(&input, voice, opts).await?;
+        Ok((key, bytes))
+    }
+
+    pub async fn marks_many(
+        &self,
+        tasks: &BTreeMap<u64, SynthInput>,
+        voice: &TTSVoice,
+        opts: &SynthesisOpts,
+    ) -> Result<BTreeMap<u64, Vec<SpeechMark>>> {
+        Ok(join_all(
+            tasks
+                .iter()
+                .map(|(k, v)| self.marks_one(*k, v.clone(), voice, opts)),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<Vec<(u64, Vec<SpeechMark>)>>>()?
+        .into_iter()
+        .collect::<BTreeMap<u64, Vec<SpeechMark>>>())
+    }
+
+    pub async fn marks_one(
+        &self,
+        key: u64,
+        input: SynthInput,
+        voice: &TTSVoice,
+        opts: &SynthesisOpts,
+    ) -> Result<(u64, Vec<SpeechMark>)> {
+        let marks = self.backend.synthesize_marks(&input, voice, opts).await?;
+        Ok((key, marks))
+    }
+}
@@ -0,0 +1,64 @@
+use super::{Gender, OutputFormat, SpeechSynthesizer, SynthInput, SynthesisOpts, TTSVoice};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use tokio::process::Command;
+
+// Shells out to the local `espeak` binary, so decks can be built without AWS credentials.
+pub struct EspeakSynthesizer;
+
+impl EspeakSynthesizer {
+    pub fn new() -> Result<Self> {
+        Ok(EspeakSynthesizer)
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for EspeakSynthesizer {
+    async fn synthesize(&self, input: &SynthInput, voice: &TTSVoice, opts: &SynthesisOpts) -> Result<Bytes> {
+        if opts.output_format != OutputFormat::default() {
+            bail!(
+                "The offline backend only produces {}; pass --backend polly for other formats",
+                OutputFormat::default().extension()
+            );
+        }
+        let output = Command::new("espeak")
+            .arg("-v")
+            .arg(&voice.id)
+            .arg("--stdout")
+            .arg(&input.text)
+            .output()
+            .await?;
+        if !output.status.success() {
+            bail!("espeak exited with status {}", output.status);
+        }
+        Ok(Bytes::from(output.stdout))
+    }
+
+    async fn list_voices(&self) -> Result<Vec<TTSVoice>> {
+        let output = Command::new("espeak").arg("--voices").output().await?;
+        if !output.status.success() {
+            bail!("espeak exited with status {}", output.status);
+        }
+        let listing = String::from_utf8_lossy(&output.stdout);
+        Ok(listing
+            .lines()
+            // First line is a header: "Pty Language Age/Gender VoiceName File Other Languages"
+            .skip(1)
+            .filter_map(|line| {
+                let mut cols = line.split_whitespace();
+                cols.next()?; // Pty
+                let code = cols.next()?.to_string();
+                cols.next()?; // Age/Gender
+                let id = cols.next()?.to_string();
+                Some(TTSVoice {
+                    id,
+                    gender: Gender::Other,
+                    language: code.clone(),
+                    code: code.parse().ok()?,
+                    neural: false,
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,159 @@
+use super::{Gender, OutputFormat, SpeechMark, SpeechSynthesizer, SynthInput, SynthesisOpts, TTSVoice};
+use crate::aws;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use rusoto_polly::{DescribeVoicesInput, Polly, PollyClient, SynthesizeSpeechInput, Voice};
+use std::{convert::TryFrom, str::FromStr};
+
+pub struct PollySynthesizer {
+    client: PollyClient,
+}
+
+impl PollySynthesizer {
+    pub fn new() -> Result<Self> {
+        let client = aws::new_client(PollyClient::new_with)?;
+        Ok(PollySynthesizer { client })
+    }
+}
+
+impl TTSVoice {
+    fn new_from_voice(v: Voice) -> Option<Self> {
+        let id = v.id?;
+        let gender = Gender::from_str(&v.gender?).unwrap_or(Gender::Other);
+        let language = v.language_name?;
+        let code = v.language_code?.parse().ok()?;
+        let eng = match v.supported_engines {
+            None => vec![],
+            Some(engs) => engs,
+        };
+        let neural = eng.iter().any(|e| e.to_lowercase() == "neural");
+        Some(Self {
+            id,
+            gender,
+            language,
+            code,
+            neural,
+        })
+    }
+}
+
+impl TryFrom<Voice> for TTSVoice {
+    type Error = anyhow::Error;
+
+    fn try_from(v: Voice) -> Result<Self, Self::Error> {
+        if let Some(ttsv) = TTSVoice::new_from_voice(v) {
+            Ok(ttsv)
+        } else {
+            bail!("Unable to convert Polly Voice to TTSVoice");
+        }
+    }
+}
+
+fn polly_output_format(format: OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Mp3 => "mp3",
+        OutputFormat::Ogg => "ogg_vorbis",
+        OutputFormat::Pcm => "pcm",
+    }
+}
+
+fn validate_sample_rate(format: OutputFormat, neural: bool, sample_rate: &str) -> Result<()> {
+    let allowed: &[&str] = match (format, neural) {
+        (OutputFormat::Pcm, true) => &["16000"],
+        (OutputFormat::Pcm, false) => &["8000", "16000"],
+        (_, true) => &["16000", "24000"],
+        (_, false) => &["8000", "16000", "22050", "24000"],
+    };
+    if allowed.contains(&sample_rate) {
+        Ok(())
+    } else {
+        bail!(
+            "Sample rate {} is not valid for {} output on the {} engine (expected one of {:?})",
+            sample_rate,
+            format.extension(),
+            if neural { "neural" } else { "standard" },
+            allowed
+        );
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for PollySynthesizer {
+    async fn synthesize(&self, input: &SynthInput, voice: &TTSVoice, opts: &SynthesisOpts) -> Result<Bytes> {
+        if let Some(sample_rate) = &opts.sample_rate {
+            validate_sample_rate(opts.output_format, opts.neural, sample_rate)?;
+        }
+        let request = SynthesizeSpeechInput {
+            engine: if opts.neural {
+                Some("neural".to_string())
+            } else {
+                Some("standard".to_string())
+            },
+            language_code: None,
+            lexicon_names: opts.lexicon_names.clone(),
+            output_format: polly_output_format(opts.output_format).to_string(),
+            sample_rate: opts.sample_rate.clone(),
+            speech_mark_types: None,
+            text: input.text.clone(),
+            text_type: input.text_type.clone(),
+            voice_id: voice.id.clone(),
+        };
+        let result = self.client.synthesize_speech(request).await?;
+        match result.audio_stream {
+            Some(bytes) => Ok(bytes),
+            None => bail!("Unable to get bytes from result."),
+        }
+    }
+
+    async fn synthesize_marks(
+        &self,
+        input: &SynthInput,
+        voice: &TTSVoice,
+        opts: &SynthesisOpts,
+    ) -> Result<Vec<SpeechMark>> {
+        let request = SynthesizeSpeechInput {
+            engine: if opts.neural {
+                Some("neural".to_string())
+            } else {
+                Some("standard".to_string())
+            },
+            language_code: None,
+            lexicon_names: opts.lexicon_names.clone(),
+            output_format: "json".to_string(),
+            sample_rate: None,
+            speech_mark_types: Some(vec!["word".to_string(), "sentence".to_string()]),
+            text: input.text.clone(),
+            text_type: input.text_type.clone(),
+            voice_id: voice.id.clone(),
+        };
+        let result = self.client.synthesize_speech(request).await?;
+        let bytes = match result.audio_stream {
+            Some(bytes) => bytes,
+            None => bail!("Unable to get speech marks from result."),
+        };
+        std::str::from_utf8(&bytes)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str::<SpeechMark>(line)?))
+            .collect::<Result<Vec<SpeechMark>>>()
+    }
+
+    async fn list_voices(&self) -> Result<Vec<TTSVoice>> {
+        let input = DescribeVoicesInput {
+            engine: None,
+            include_additional_language_codes: Some(false),
+            language_code: None,
+            next_token: None,
+        };
+        let request_result = self.client.describe_voices(input).await?;
+        if let Some(polly_voices) = request_result.voices {
+            polly_voices
+                .into_iter()
+                .map(TTSVoice::try_from)
+                .collect::<Result<Vec<TTSVoice>>>()
+        } else {
+            bail!("No voices returned");
+        }
+    }
+}